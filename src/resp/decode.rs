@@ -0,0 +1,336 @@
+// incremental, frame-at-a-time decoding for all resp types
+/*
+- resp frame
+    - simple string: "+OK\r\n"
+    - error: "-Error message\r\n"
+    - bulk error: "!<length>\r\n<error>\r\n"
+    - integer: ":[<+|->]<value>\r\n"
+    - bulk string: "$<length>\r\n<data>\r\n"
+    - null bulk string: "$-1\r\n"
+    - array: "*<number-of-elements>\r\n<element-1>...<element-n>"
+    - null array: "*-1\r\n"
+    - null: "_\r\n"
+    - boolean: "#<t|f>\r\n"
+    - double: ",[<+|->]<integral>[.<fractional>][<E|e>[sign]<exponent>]\r\n"
+    - map: "%<number-of-entries>\r\n<key-1><value-1>...<key-n><value-n>"
+    - set: "~<number-of-elements>\r\n<element-1>...<element-n>"
+*/
+
+use std::io::Read;
+
+use super::*;
+
+const CRLF: &[u8] = b"\r\n";
+
+/// Pull decoder over a byte source, modeled on `Decoder::new(reader)`: it owns a
+/// growing buffer, parses one frame at a time and, on a partial frame, refills
+/// from the reader without ever advancing past the bytes it has already handed
+/// back. A connection loop can also read [`RespDecoder::consumed`] to drain its
+/// buffer up to the last complete frame.
+pub struct RespDecoder<R> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> RespDecoder<R> {
+    pub fn new(reader: R) -> Self {
+        RespDecoder {
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Total number of bytes consumed by the frames decoded so far.
+    pub fn consumed(&self) -> usize {
+        self.pos
+    }
+
+    /// Decode the next frame, refilling from the reader until a whole frame is
+    /// available. Returns `Incomplete` when the reader hits EOF before a whole
+    /// frame is buffered, including a clean EOF between frames.
+    pub fn decode(&mut self) -> Result<RespFrame, RespError> {
+        loop {
+            match parse_frame(&self.buf[self.pos..]) {
+                Ok((frame, consumed)) => {
+                    self.pos += consumed;
+                    return Ok(frame);
+                }
+                Err(RespError::Incomplete) => {
+                    if !self.fill()? {
+                        return Err(RespError::Incomplete);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn fill(&mut self) -> Result<bool, RespError> {
+        let mut chunk = [0u8; 4096];
+        let n = self.reader.read(&mut chunk).map_err(RespError::Io)?;
+        if n == 0 {
+            return Ok(false);
+        }
+        self.buf.extend_from_slice(&chunk[..n]);
+        Ok(true)
+    }
+}
+
+impl RespFrame {
+    /// Parse a single frame from the front of `data`, returning the frame and
+    /// the number of bytes it consumed. `Incomplete` means `data` holds only
+    /// part of a frame, so the cursor must stay put until more bytes arrive.
+    pub fn parse(data: &[u8]) -> Result<(RespFrame, usize), RespError> {
+        parse_frame(data)
+    }
+}
+
+fn parse_frame(data: &[u8]) -> Result<(RespFrame, usize), RespError> {
+    let first = *data.first().ok_or(RespError::Incomplete)?;
+    match first {
+        b'+' => {
+            let (line, end) = read_line(&data[1..])?;
+            Ok((RespFrame::SimpleString(SimpleString(parse_str(line)?)), 1 + end))
+        }
+        b'-' => {
+            let (line, end) = read_line(&data[1..])?;
+            Ok((RespFrame::SimpleError(SimpleError(parse_str(line)?)), 1 + end))
+        }
+        b':' => {
+            let (line, end) = read_line(&data[1..])?;
+            let v = parse_str(line)?
+                .parse::<i64>()
+                .map_err(|_| RespError::Syntax("invalid integer"))?;
+            Ok((RespFrame::Integer(v), 1 + end))
+        }
+        b'_' => {
+            let (_, end) = read_line(&data[1..])?;
+            Ok((RespFrame::Null(RespNull), 1 + end))
+        }
+        b'#' => {
+            let (line, end) = read_line(&data[1..])?;
+            let b = match line {
+                b"t" => true,
+                b"f" => false,
+                _ => return Err(RespError::Syntax("invalid boolean")),
+            };
+            Ok((RespFrame::Boolean(b), 1 + end))
+        }
+        b',' => {
+            let (line, end) = read_line(&data[1..])?;
+            let v = parse_str(line)?
+                .parse::<f64>()
+                .map_err(|_| RespError::Syntax("invalid double"))?;
+            Ok((RespFrame::Double(v), 1 + end))
+        }
+        b'$' => parse_bulk_string(data),
+        b'!' => parse_bulk_error(data),
+        b'*' => parse_array(data),
+        b'%' => parse_map(data),
+        b'~' => parse_set(data),
+        _ => Err(RespError::Syntax("invalid type byte")),
+    }
+}
+
+// $<length>\r\n<data>\r\n, with a negative length decoding to a null bulk string.
+fn parse_bulk_string(data: &[u8]) -> Result<(RespFrame, usize), RespError> {
+    let (line, header) = read_line(&data[1..])?;
+    let len = parse_int(line)?;
+    let start = 1 + header;
+    if len < 0 {
+        return Ok((RespFrame::NullBulkString(RespNullBulkString), start));
+    }
+    let len = len as usize;
+    if data.len() < start + len + 2 {
+        return Err(RespError::Incomplete);
+    }
+    if &data[start + len..start + len + 2] != CRLF {
+        return Err(RespError::Syntax("missing CRLF terminator"));
+    }
+    let frame = BulkString(data[start..start + len].to_vec());
+    Ok((RespFrame::BulkString(frame), start + len + 2))
+}
+
+// !<length>\r\n<error>\r\n
+fn parse_bulk_error(data: &[u8]) -> Result<(RespFrame, usize), RespError> {
+    let (line, header) = read_line(&data[1..])?;
+    let len = parse_int(line)?;
+    if len < 0 {
+        return Err(RespError::Syntax("negative bulk error length"));
+    }
+    let start = 1 + header;
+    let len = len as usize;
+    if data.len() < start + len + 2 {
+        return Err(RespError::Incomplete);
+    }
+    if &data[start + len..start + len + 2] != CRLF {
+        return Err(RespError::Syntax("missing CRLF terminator"));
+    }
+    let frame = BulkError(parse_str(&data[start..start + len])?);
+    Ok((RespFrame::BulkError(frame), start + len + 2))
+}
+
+// *<count>\r\n<element>..., with a negative count decoding to a null array.
+fn parse_array(data: &[u8]) -> Result<(RespFrame, usize), RespError> {
+    let (line, header) = read_line(&data[1..])?;
+    let count = parse_int(line)?;
+    let mut pos = 1 + header;
+    if count < 0 {
+        return Ok((RespFrame::NullArray(RespNullArray), pos));
+    }
+    let mut frames = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let (frame, n) = parse_frame(&data[pos..])?;
+        pos += n;
+        frames.push(frame);
+    }
+    Ok((RespFrame::Array(Array(frames)), pos))
+}
+
+// %<count>\r\n<key><value>..., keys are always simple strings.
+fn parse_map(data: &[u8]) -> Result<(RespFrame, usize), RespError> {
+    let (line, header) = read_line(&data[1..])?;
+    let count = parse_int(line)?;
+    let mut pos = 1 + header;
+    let mut map = BTreeMap::new();
+    for _ in 0..count.max(0) {
+        let (key, n) = parse_frame(&data[pos..])?;
+        pos += n;
+        let key = match key {
+            RespFrame::SimpleString(s) => s.0,
+            _ => return Err(RespError::Syntax("map key must be a simple string")),
+        };
+        let (value, n) = parse_frame(&data[pos..])?;
+        pos += n;
+        map.insert(key, value);
+    }
+    Ok((RespFrame::Map(Map(map)), pos))
+}
+
+// ~<count>\r\n<element>...; the wire form only carries element values, so they
+// are keyed by their ordinal position when decoded back into the map.
+fn parse_set(data: &[u8]) -> Result<(RespFrame, usize), RespError> {
+    let (line, header) = read_line(&data[1..])?;
+    let count = parse_int(line)?;
+    let mut pos = 1 + header;
+    let mut map = BTreeMap::new();
+    for i in 0..count.max(0) {
+        let (value, n) = parse_frame(&data[pos..])?;
+        pos += n;
+        map.insert(i.to_string(), value);
+    }
+    Ok((RespFrame::Set(Set(map)), pos))
+}
+
+fn read_line(data: &[u8]) -> Result<(&[u8], usize), RespError> {
+    match data.windows(2).position(|w| w == CRLF) {
+        Some(pos) => Ok((&data[..pos], pos + 2)),
+        None => Err(RespError::Incomplete),
+    }
+}
+
+fn parse_str(data: &[u8]) -> Result<String, RespError> {
+    Ok(std::str::from_utf8(data)?.to_string())
+}
+
+fn parse_int(data: &[u8]) -> Result<i64, RespError> {
+    parse_str(data)?
+        .parse::<i64>()
+        .map_err(|_| RespError::InvalidLength)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_simple_string_decode() {
+        let (frame, n) = RespFrame::parse(b"+OK\r\n").unwrap();
+        assert_eq!(frame, RespFrame::SimpleString(SimpleString("OK".to_string())));
+        assert_eq!(n, 5);
+    }
+
+    #[test]
+    fn test_simple_error_decode() {
+        let (frame, n) = RespFrame::parse(b"-Error message\r\n").unwrap();
+        assert_eq!(
+            frame,
+            RespFrame::SimpleError(SimpleError("Error message".to_string()))
+        );
+        assert_eq!(n, 16);
+    }
+
+    #[test]
+    fn test_integer_decode() {
+        let (frame, _) = RespFrame::parse(b":+123\r\n").unwrap();
+        assert_eq!(frame, RespFrame::Integer(123));
+        let (frame, _) = RespFrame::parse(b":-123\r\n").unwrap();
+        assert_eq!(frame, RespFrame::Integer(-123));
+    }
+
+    #[test]
+    fn test_bulk_string_decode() {
+        let (frame, n) = RespFrame::parse(b"$5\r\nhello\r\n").unwrap();
+        assert_eq!(frame, RespFrame::BulkString(BulkString(b"hello".to_vec())));
+        assert_eq!(n, 11);
+    }
+
+    #[test]
+    fn test_bulk_string_incomplete_does_not_advance() {
+        // the length says 5 bytes but only 3 are buffered
+        assert!(matches!(
+            RespFrame::parse(b"$5\r\nhel"),
+            Err(RespError::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn test_line_incomplete_without_crlf() {
+        assert!(matches!(
+            RespFrame::parse(b"+OK"),
+            Err(RespError::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn test_array_decode() {
+        let (frame, n) = RespFrame::parse(b"*2\r\n$3\r\nget\r\n$5\r\nhello\r\n").unwrap();
+        assert_eq!(
+            frame,
+            RespFrame::Array(Array(vec![
+                RespFrame::BulkString(BulkString(b"get".to_vec())),
+                RespFrame::BulkString(BulkString(b"hello".to_vec())),
+            ]))
+        );
+        assert_eq!(n, 24);
+    }
+
+    #[test]
+    fn test_null_and_boolean_decode() {
+        assert_eq!(RespFrame::parse(b"_\r\n").unwrap().0, RespFrame::Null(RespNull));
+        assert_eq!(RespFrame::parse(b"#t\r\n").unwrap().0, RespFrame::Boolean(true));
+        assert_eq!(RespFrame::parse(b"#f\r\n").unwrap().0, RespFrame::Boolean(false));
+    }
+
+    #[test]
+    fn test_invalid_type_byte_is_syntax_error() {
+        assert!(matches!(
+            RespFrame::parse(b"?nope\r\n"),
+            Err(RespError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_decoder_reads_frames_in_sequence() {
+        let mut decoder = RespDecoder::new(Cursor::new(b"+OK\r\n:+7\r\n".to_vec()));
+        assert_eq!(
+            decoder.decode().unwrap(),
+            RespFrame::SimpleString(SimpleString("OK".to_string()))
+        );
+        assert_eq!(decoder.decode().unwrap(), RespFrame::Integer(7));
+        assert_eq!(decoder.consumed(), 10);
+    }
+}
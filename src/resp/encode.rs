@@ -22,124 +22,180 @@ use super::*;
 
 // simple string: "+OK\r\n"
 impl RespEncode for SimpleString {
-    fn encode(self) -> Vec<u8> {
-        format!("+{}\r\n", self.0).into_bytes()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(format!("+{}\r\n", self.0).as_bytes());
     }
 }
 
 // error: "-Error message\r\n"
 impl RespEncode for SimpleError {
-    fn encode(self) -> Vec<u8> {
-        format!("-{}\r\n", self.0).into_bytes()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(format!("-{}\r\n", self.0).as_bytes());
     }
 }
 
 // integer: ":[<+|->]<value>\r\n"
 impl RespEncode for i64 {
-    fn encode(self) -> Vec<u8> {
-        let sign = if self < 0 { "-" } else { "+" };
-        format!(":{}{}\r\n", sign, self.abs()).into_bytes()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        let s = if *self < 0 {
+            format!(":{self}\r\n")
+        } else {
+            format!(":+{self}\r\n")
+        };
+        buf.extend_from_slice(s.as_bytes());
     }
 }
 
 // bulk string: "$<length>\r\n<data>\r\n"
 impl RespEncode for BulkString {
-    fn encode(self) -> Vec<u8> {
-        format!(
-            "${}\r\n{}\r\n",
-            self.len(),
-            String::from_utf8(self.0).unwrap()
-        )
-        .into_bytes()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(format!("${}\r\n", self.len()).as_bytes());
+        buf.extend_from_slice(&self.0);
+        buf.extend_from_slice(b"\r\n");
     }
 }
 
 // bulk error: "!<length>\r\n<error>\r\n"
 impl RespEncode for BulkError {
-    fn encode(self) -> Vec<u8> {
-        let mut buff = Vec::with_capacity(self.len() + 16);
-        buff.extend_from_slice(&format!("!{}\r\n", self.len()).into_bytes());
-        buff.extend_from_slice(self.as_bytes());
-        buff.extend_from_slice(b"\r\n");
-        buff
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(format!("!{}\r\n", self.len()).as_bytes());
+        buf.extend_from_slice(self.as_bytes());
+        buf.extend_from_slice(b"\r\n");
     }
 }
 
 // array: "*<number-of-elements>\r\n<element-1>...<element-n>"
 impl RespEncode for Array {
-    fn encode(self) -> Vec<u8> {
-        let mut buff = Vec::with_capacity(16);
-        buff.extend_from_slice(&format!("*{}\r\n", self.0.len()).into_bytes());
-        for frame in self.0 {
-            buff.extend_from_slice(&frame.encode());
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(format!("*{}\r\n", self.0.len()).as_bytes());
+        for frame in &self.0 {
+            frame.encode_into(buf);
+        }
+    }
+
+    fn encode_into_with(&self, buf: &mut Vec<u8>, version: RespVersion) {
+        buf.extend_from_slice(format!("*{}\r\n", self.0.len()).as_bytes());
+        for frame in &self.0 {
+            frame.encode_into_with(buf, version);
         }
-        buff
     }
 }
 
 // null bulk string: "$-1\r\n"
 impl RespEncode for RespNullBulkString {
-    fn encode(self) -> Vec<u8> {
-        "$-1\r\n".as_bytes().to_vec()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(b"$-1\r\n");
     }
 }
 
 // null array: "*-1\r\n"
 impl RespEncode for RespNullArray {
-    fn encode(self) -> Vec<u8> {
-        "*-1\r\n".as_bytes().to_vec()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(b"*-1\r\n");
     }
 }
 
-// null: "_\r\n"
+// null: "_\r\n" (RESP2: "$-1\r\n")
 impl RespEncode for RespNull {
-    fn encode(self) -> Vec<u8> {
-        "_\r\n".as_bytes().to_vec()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(b"_\r\n");
+    }
+
+    fn encode_into_with(&self, buf: &mut Vec<u8>, version: RespVersion) {
+        match version {
+            RespVersion::V2 => buf.extend_from_slice(b"$-1\r\n"),
+            RespVersion::V3 => self.encode_into(buf),
+        }
     }
 }
 
-// boolean: "#<t|f>\r\n"
+// boolean: "#<t|f>\r\n" (RESP2: ":1\r\n" / ":0\r\n")
 impl RespEncode for bool {
-    fn encode(self) -> Vec<u8> {
-        format!("#{}\r\n", if self { "t" } else { "f" }).into_bytes()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(if *self { b"#t\r\n" } else { b"#f\r\n" });
+    }
+
+    fn encode_into_with(&self, buf: &mut Vec<u8>, version: RespVersion) {
+        match version {
+            RespVersion::V2 => buf.extend_from_slice(if *self { b":1\r\n" } else { b":0\r\n" }),
+            RespVersion::V3 => self.encode_into(buf),
+        }
     }
 }
 
 // double: ",[<+|->]<integral>[.<fractional>][<E|e>[sign]<exponent>]\r\n"
+// (RESP2: the same textual form carried in a bulk string)
 impl RespEncode for f64 {
-    fn encode(self) -> Vec<u8> {
-        let ret = if self.abs() > 1e+8 || self.abs() < 1e-8 {
-            format!(",{:e}\r\n", self)
-        } else {
-            format!(",{}\r\n", self)
-        };
-        ret.into_bytes()
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(format!(",{}\r\n", format_double(*self)).as_bytes());
+    }
+
+    fn encode_into_with(&self, buf: &mut Vec<u8>, version: RespVersion) {
+        match version {
+            RespVersion::V2 => {
+                let text = format_double(*self);
+                buf.extend_from_slice(format!("${}\r\n{}\r\n", text.len(), text).as_bytes());
+            }
+            RespVersion::V3 => self.encode_into(buf),
+        }
+    }
+}
+
+// the textual double body, shared by the RESP3 and RESP2 (bulk string) forms
+fn format_double(v: f64) -> String {
+    if v.abs() > 1e+8 || v.abs() < 1e-8 {
+        format!("{:e}", v)
+    } else {
+        format!("{}", v)
     }
 }
 
 // map: "%<number-of-entries>\r\n<key-1><value-1>...<key-n><value-n>"
 // only support simple string key
 impl RespEncode for Map {
-    fn encode(self) -> Vec<u8> {
-        let mut buff = Vec::with_capacity(16);
-        buff.extend_from_slice(&format!("%{}\r\n", self.len()).into_bytes());
-        for (key, value) in self.0 {
-            buff.extend_from_slice(&SimpleString(key).encode());
-            buff.extend_from_slice(&value.encode());
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(format!("%{}\r\n", self.len()).as_bytes());
+        for (key, value) in &self.0 {
+            buf.extend_from_slice(format!("+{}\r\n", key).as_bytes());
+            value.encode_into(buf);
+        }
+    }
+
+    // RESP2: a flat array of alternating key/value elements.
+    fn encode_into_with(&self, buf: &mut Vec<u8>, version: RespVersion) {
+        match version {
+            RespVersion::V2 => {
+                buf.extend_from_slice(format!("*{}\r\n", self.len() * 2).as_bytes());
+                for (key, value) in &self.0 {
+                    buf.extend_from_slice(format!("+{}\r\n", key).as_bytes());
+                    value.encode_into_with(buf, version);
+                }
+            }
+            RespVersion::V3 => self.encode_into(buf),
         }
-        buff
     }
 }
 
 // set: "~<number-of-elements>\r\n<element-1>...<element-n>"
 impl RespEncode for Set {
-    fn encode(self) -> Vec<u8> {
-        let mut buff = Vec::with_capacity(16);
-        buff.extend_from_slice(&format!("~{}\r\n", self.len()).into_bytes());
-        for frame in self.0 {
-            buff.extend_from_slice(&frame.1.encode());
+    fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(format!("~{}\r\n", self.len()).as_bytes());
+        for value in self.0.values() {
+            value.encode_into(buf);
+        }
+    }
+
+    // RESP2: a flat array of the element values.
+    fn encode_into_with(&self, buf: &mut Vec<u8>, version: RespVersion) {
+        match version {
+            RespVersion::V2 => {
+                buf.extend_from_slice(format!("*{}\r\n", self.len()).as_bytes());
+                for value in self.0.values() {
+                    value.encode_into_with(buf, version);
+                }
+            }
+            RespVersion::V3 => self.encode_into(buf),
         }
-        buff
     }
 }
 
@@ -166,6 +222,10 @@ mod tests {
 
         let frame = -123;
         assert_eq!(frame.encode(), b":-123\r\n");
+
+        // i64::MIN must not overflow on the encode path.
+        let frame = i64::MIN;
+        assert_eq!(frame.encode(), b":-9223372036854775808\r\n");
     }
 
     #[test]
@@ -249,6 +309,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_into_shared_buffer() {
+        let mut buf = Vec::new();
+        SimpleString("OK".to_string()).encode_into(&mut buf);
+        123i64.encode_into(&mut buf);
+        assert_eq!(buf, b"+OK\r\n:+123\r\n");
+    }
+
+    #[test]
+    fn test_v2_downgrades() {
+        assert_eq!(true.encode_with(RespVersion::V2), b":1\r\n");
+        assert_eq!(false.encode_with(RespVersion::V2), b":0\r\n");
+        assert_eq!(RespNull.encode_with(RespVersion::V2), b"$-1\r\n");
+        assert_eq!(123.456f64.encode_with(RespVersion::V2), b"$7\r\n123.456\r\n");
+    }
+
+    #[test]
+    fn test_v3_matches_default() {
+        assert_eq!(true.encode_with(RespVersion::V3), b"#t\r\n");
+        assert_eq!(RespNull.encode_with(RespVersion::V3), b"_\r\n");
+        assert_eq!(123.456f64.encode_with(RespVersion::V3), b",123.456\r\n");
+    }
+
+    #[test]
+    fn test_map_flattened_in_v2() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "key1".to_string(),
+            RespFrame::BulkString(BulkString(b"value1".to_vec())),
+        );
+        let frame = Map(map);
+        assert_eq!(
+            frame.encode_with(RespVersion::V2),
+            b"*2\r\n+key1\r\n$6\r\nvalue1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_set_flattened_in_v2() {
+        let mut map = BTreeMap::new();
+        map.insert("0".to_string(), RespFrame::Boolean(true));
+        let frame = Set(map);
+        assert_eq!(frame.encode_with(RespVersion::V2), b"*1\r\n:1\r\n");
+    }
+
     #[test]
     fn test_set_encode() {
         let mut map = BTreeMap::new();
@@ -0,0 +1,61 @@
+use std::{fmt, str::Utf8Error};
+
+/// Errors produced while encoding or decoding resp frames.
+///
+/// The variants let a caller tell apart the outcomes that demand different
+/// handling: `Incomplete` means "wait for more bytes", `Syntax`/`InvalidLength`
+/// mean "drop the connection", and `Io` surfaces a failure of the underlying
+/// reader or writer.
+#[derive(Debug)]
+pub enum RespError {
+    /// Ran out of bytes in the middle of a frame; retry once more arrive.
+    Incomplete,
+    /// The bytes are not valid resp.
+    Syntax(&'static str),
+    /// A length or count prefix could not be parsed as a number.
+    InvalidLength,
+    /// The underlying reader or writer failed.
+    Io(std::io::Error),
+    /// A message raised by `serde` while (de)serializing a Rust type.
+    Custom(String),
+}
+
+impl RespError {
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, RespError::Incomplete)
+    }
+
+    pub fn is_syntax(&self) -> bool {
+        matches!(self, RespError::Syntax(_) | RespError::InvalidLength)
+    }
+
+    pub fn is_io(&self) -> bool {
+        matches!(self, RespError::Io(_))
+    }
+}
+
+impl fmt::Display for RespError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RespError::Incomplete => write!(f, "incomplete frame"),
+            RespError::Syntax(msg) => write!(f, "syntax error: {}", msg),
+            RespError::InvalidLength => write!(f, "invalid length prefix"),
+            RespError::Io(e) => write!(f, "io error: {}", e),
+            RespError::Custom(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RespError {}
+
+impl From<std::io::Error> for RespError {
+    fn from(e: std::io::Error) -> Self {
+        RespError::Io(e)
+    }
+}
+
+impl From<Utf8Error> for RespError {
+    fn from(_: Utf8Error) -> Self {
+        RespError::Syntax("invalid utf-8")
+    }
+}
@@ -8,6 +8,12 @@ use enum_dispatch::enum_dispatch;
 
 mod decode;
 mod encode;
+mod error;
+mod serde_bridge;
+
+pub use decode::RespDecoder;
+pub use error::RespError;
+pub use serde_bridge::{from_resp, to_resp, RespDeserializer, RespSerializer};
 
 /*
 - resp frame
@@ -33,11 +39,57 @@ mod encode;
 
 #[enum_dispatch]
 pub trait RespEncode {
-    fn encode(self) -> Vec<u8>;
+    /// Append the encoded frame to `buf`, letting containers fill a single
+    /// shared buffer so nested frames need no intermediate allocations.
+    fn encode_into(&self, buf: &mut Vec<u8>);
+
+    /// Encode the frame into a freshly allocated buffer.
+    fn encode(self) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut buf = Vec::new();
+        self.encode_into(&mut buf);
+        buf
+    }
+
+    /// Append the encoded frame to `buf`, downgrading the forms listed on
+    /// [`RespVersion`] when `version` is [`RespVersion::V2`]. The default
+    /// ignores `version`; only the types that differ between versions override
+    /// it.
+    fn encode_into_with(&self, buf: &mut Vec<u8>, version: RespVersion) {
+        let _ = version;
+        self.encode_into(buf);
+    }
+
+    /// Encode the frame for a given protocol version into a fresh buffer.
+    fn encode_with(self, version: RespVersion) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut buf = Vec::new();
+        self.encode_into_with(&mut buf, version);
+        buf
+    }
+}
+
+/// The RESP protocol version an encoder targets. `V3` is the native form of
+/// every frame; `V2` downgrades booleans, doubles, nulls, maps and sets to
+/// RESP2 forms. Bulk errors and all other frames are written as in `V3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RespVersion {
+    V2,
+    V3,
 }
 
 pub trait RespDecode {
-    fn decode(data: &[u8]) -> Result<RespFrame, String>;
+    fn decode(data: &[u8]) -> Result<RespFrame, RespError>;
+}
+
+impl RespDecode for RespFrame {
+    fn decode(data: &[u8]) -> Result<RespFrame, RespError> {
+        RespFrame::parse(data).map(|(frame, _)| frame)
+    }
 }
 
 #[derive(Debug, PartialEq, PartialOrd)]
@@ -231,15 +283,19 @@ impl From<Vec<RespFrame>> for Array {
     }
 }
 
-impl From<Vec<u8>> for BulkError {
-    fn from(v: Vec<u8>) -> Self {
-        BulkError(String::from_utf8(v).unwrap())
+impl TryFrom<Vec<u8>> for BulkError {
+    type Error = RespError;
+
+    fn try_from(v: Vec<u8>) -> Result<Self, Self::Error> {
+        Ok(BulkError(String::from_utf8(v).map_err(|e| e.utf8_error())?))
     }
 }
 
-impl From<&[u8]> for BulkError {
-    fn from(v: &[u8]) -> Self {
-        BulkError(String::from_utf8(v.to_vec()).unwrap())
+impl TryFrom<&[u8]> for BulkError {
+    type Error = RespError;
+
+    fn try_from(v: &[u8]) -> Result<Self, Self::Error> {
+        Ok(BulkError(std::str::from_utf8(v)?.to_string()))
     }
 }
 
@@ -0,0 +1,790 @@
+//! A `serde` bridge mapping arbitrary Rust types onto [`RespFrame`] values.
+//!
+//! [`to_resp`] drives a [`RespSerializer`] that lowers primitives and containers
+//! onto the existing frame variants; [`from_resp`] walks a frame back into any
+//! `Deserialize` type. Both surface failures through [`RespError`], which plays
+//! the role of serde's associated error type.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{
+    de::{
+        self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+        SeqAccess, VariantAccess, Visitor,
+    },
+    ser::{self, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+        SerializeTuple, SerializeTupleStruct, SerializeTupleVariant},
+    Deserializer, Serialize,
+};
+
+use super::*;
+
+impl ser::Error for RespError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        RespError::Custom(msg.to_string())
+    }
+}
+
+impl de::Error for RespError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        RespError::Custom(msg.to_string())
+    }
+}
+
+/// Serialize any `Serialize` value into a [`RespFrame`].
+pub fn to_resp<T: Serialize>(value: &T) -> Result<RespFrame, RespError> {
+    value.serialize(RespSerializer)
+}
+
+/// Deserialize a [`RespFrame`] into any owned `Deserialize` type.
+pub fn from_resp<T: DeserializeOwned>(frame: RespFrame) -> Result<T, RespError> {
+    T::deserialize(RespDeserializer { frame: &frame })
+}
+
+// -- serializer -----------------------------------------------------------
+
+/// Lowers Rust values onto [`RespFrame`] variants.
+pub struct RespSerializer;
+
+impl serde::Serializer for RespSerializer {
+    type Ok = RespFrame;
+    type Error = RespError;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::Boolean(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<RespFrame, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<RespFrame, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<RespFrame, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::Integer(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<RespFrame, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<RespFrame, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<RespFrame, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::Integer(v as i64))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<RespFrame, RespError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::Double(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<RespFrame, RespError> {
+        self.serialize_str(&v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::BulkString(BulkString(v.as_bytes().to_vec())))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::BulkString(BulkString(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::Null(RespNull))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<RespFrame, RespError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::Null(RespNull))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<RespFrame, RespError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<RespFrame, RespError> {
+        Ok(RespFrame::SimpleString(SimpleString(variant.to_string())))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<RespFrame, RespError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<RespFrame, RespError> {
+        let mut map = BTreeMap::new();
+        map.insert(variant.to_string(), value.serialize(RespSerializer)?);
+        Ok(RespFrame::Map(Map(map)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, RespError> {
+        Ok(SeqSerializer {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, RespError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, RespError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, RespError> {
+        Ok(SeqSerializer {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer, RespError> {
+        Ok(MapSerializer {
+            variant: None,
+            entries: BTreeMap::new(),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<MapSerializer, RespError> {
+        self.serialize_map(None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<MapSerializer, RespError> {
+        Ok(MapSerializer {
+            variant: Some(variant),
+            entries: BTreeMap::new(),
+            next_key: None,
+        })
+    }
+}
+
+/// Collects sequence and tuple elements into an [`Array`], wrapping the result
+/// in a single-entry [`Map`] for externally tagged tuple variants.
+pub struct SeqSerializer {
+    variant: Option<&'static str>,
+    items: Vec<RespFrame>,
+}
+
+impl SeqSerializer {
+    fn finish(self) -> RespFrame {
+        let array = RespFrame::Array(Array(self.items));
+        match self.variant {
+            Some(variant) => {
+                let mut map = BTreeMap::new();
+                map.insert(variant.to_string(), array);
+                RespFrame::Map(Map(map))
+            }
+            None => array,
+        }
+    }
+}
+
+impl SerializeSeq for SeqSerializer {
+    type Ok = RespFrame;
+    type Error = RespError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RespError> {
+        self.items.push(value.serialize(RespSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespFrame, RespError> {
+        Ok(self.finish())
+    }
+}
+
+impl SerializeTuple for SeqSerializer {
+    type Ok = RespFrame;
+    type Error = RespError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RespError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<RespFrame, RespError> {
+        Ok(self.finish())
+    }
+}
+
+impl SerializeTupleStruct for SeqSerializer {
+    type Ok = RespFrame;
+    type Error = RespError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RespError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<RespFrame, RespError> {
+        Ok(self.finish())
+    }
+}
+
+impl SerializeTupleVariant for SeqSerializer {
+    type Ok = RespFrame;
+    type Error = RespError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RespError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<RespFrame, RespError> {
+        Ok(self.finish())
+    }
+}
+
+/// Collects map and struct entries into a [`Map`] keyed by simple strings.
+pub struct MapSerializer {
+    variant: Option<&'static str>,
+    entries: BTreeMap<String, RespFrame>,
+    next_key: Option<String>,
+}
+
+impl MapSerializer {
+    fn finish(self) -> RespFrame {
+        let map = RespFrame::Map(Map(self.entries));
+        match self.variant {
+            Some(variant) => {
+                let mut outer = BTreeMap::new();
+                outer.insert(variant.to_string(), map);
+                RespFrame::Map(Map(outer))
+            }
+            None => map,
+        }
+    }
+}
+
+impl SerializeMap for MapSerializer {
+    type Ok = RespFrame;
+    type Error = RespError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), RespError> {
+        self.next_key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RespError> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or(RespError::Syntax("map value without key"))?;
+        self.entries.insert(key, value.serialize(RespSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespFrame, RespError> {
+        Ok(self.finish())
+    }
+}
+
+impl SerializeStruct for MapSerializer {
+    type Ok = RespFrame;
+    type Error = RespError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), RespError> {
+        self.entries
+            .insert(key.to_string(), value.serialize(RespSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespFrame, RespError> {
+        Ok(self.finish())
+    }
+}
+
+impl SerializeStructVariant for MapSerializer {
+    type Ok = RespFrame;
+    type Error = RespError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), RespError> {
+        SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<RespFrame, RespError> {
+        Ok(self.finish())
+    }
+}
+
+/// Serializes a map key into the `String` a [`Map`] key requires.
+struct KeySerializer;
+
+impl serde::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = RespError;
+
+    type SerializeSeq = Impossible<String, RespError>;
+    type SerializeTuple = Impossible<String, RespError>;
+    type SerializeTupleStruct = Impossible<String, RespError>;
+    type SerializeTupleVariant = Impossible<String, RespError>;
+    type SerializeMap = Impossible<String, RespError>;
+    type SerializeStruct = Impossible<String, RespError>;
+    type SerializeStructVariant = Impossible<String, RespError>;
+
+    fn serialize_str(self, v: &str) -> Result<String, RespError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, RespError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<String, RespError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, RespError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, RespError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, RespError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, RespError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, RespError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, RespError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_none(self) -> Result<String, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<String, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_unit(self) -> Result<String, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, RespError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, RespError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, RespError> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, RespError> {
+        Err(key_error())
+    }
+}
+
+fn key_error() -> RespError {
+    RespError::Syntax("map key must serialize to a string")
+}
+
+// -- deserializer ---------------------------------------------------------
+
+/// Walks a borrowed [`RespFrame`] into a `Deserialize` type.
+#[derive(Clone, Copy)]
+pub struct RespDeserializer<'de> {
+    frame: &'de RespFrame,
+}
+
+impl<'de> serde::Deserializer<'de> for RespDeserializer<'de> {
+    type Error = RespError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespError> {
+        match self.frame {
+            RespFrame::SimpleString(s) => visitor.visit_str(&s.0),
+            RespFrame::SimpleError(s) => visitor.visit_str(&s.0),
+            RespFrame::BulkError(s) => visitor.visit_str(&s.0),
+            RespFrame::Integer(v) => visitor.visit_i64(*v),
+            RespFrame::Boolean(v) => visitor.visit_bool(*v),
+            RespFrame::Double(v) => visitor.visit_f64(*v),
+            RespFrame::BulkString(b) => match std::str::from_utf8(&b.0) {
+                Ok(s) => visitor.visit_str(s),
+                Err(_) => visitor.visit_bytes(&b.0),
+            },
+            RespFrame::Array(a) => visitor.visit_seq(SeqDeserializer(a.0.iter())),
+            RespFrame::Set(m) => visitor.visit_seq(SeqDeserializer(m.0.values())),
+            RespFrame::Map(m) => visitor.visit_map(MapDeserializer {
+                iter: m.0.iter(),
+                value: None,
+            }),
+            RespFrame::Null(_) | RespFrame::NullArray(_) | RespFrame::NullBulkString(_) => {
+                visitor.visit_unit()
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespError> {
+        match self.frame {
+            RespFrame::Null(_) | RespFrame::NullArray(_) | RespFrame::NullBulkString(_) => {
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, RespError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, RespError> {
+        visitor.visit_enum(EnumDeserializer { frame: self.frame })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct SeqDeserializer<I>(I);
+
+impl<'de, I> SeqAccess<'de> for SeqDeserializer<I>
+where
+    I: Iterator<Item = &'de RespFrame>,
+{
+    type Error = RespError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, RespError> {
+        match self.0.next() {
+            Some(frame) => seed.deserialize(RespDeserializer { frame }).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct MapDeserializer<'de> {
+    iter: std::collections::btree_map::Iter<'de, String, RespFrame>,
+    value: Option<&'de RespFrame>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+    type Error = RespError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, RespError> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key.as_str().into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, RespError> {
+        let frame = self
+            .value
+            .take()
+            .ok_or(RespError::Syntax("map value without key"))?;
+        seed.deserialize(RespDeserializer { frame })
+    }
+}
+
+struct EnumDeserializer<'de> {
+    frame: &'de RespFrame,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = RespError;
+    type Variant = VariantDeserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), RespError> {
+        match self.frame {
+            RespFrame::SimpleString(s) => {
+                let variant = seed.deserialize(de::value::StrDeserializer::<RespError>::new(&s.0))?;
+                Ok((variant, VariantDeserializer { frame: None }))
+            }
+            RespFrame::Map(m) => {
+                let (key, value) = m
+                    .0
+                    .iter()
+                    .next()
+                    .ok_or(RespError::Syntax("empty enum map"))?;
+                let variant = seed.deserialize(de::value::StrDeserializer::<RespError>::new(key))?;
+                Ok((variant, VariantDeserializer { frame: Some(value) }))
+            }
+            _ => Err(RespError::Syntax("invalid enum frame")),
+        }
+    }
+}
+
+struct VariantDeserializer<'de> {
+    frame: Option<&'de RespFrame>,
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
+    type Error = RespError;
+
+    fn unit_variant(self) -> Result<(), RespError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, RespError> {
+        let frame = self
+            .frame
+            .ok_or(RespError::Syntax("expected newtype variant value"))?;
+        seed.deserialize(RespDeserializer { frame })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, RespError> {
+        let frame = self
+            .frame
+            .ok_or(RespError::Syntax("expected tuple variant value"))?;
+        RespDeserializer { frame }.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, RespError> {
+        let frame = self
+            .frame
+            .ok_or(RespError::Syntax("expected struct variant value"))?;
+        RespDeserializer { frame }.deserialize_any(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn test_primitives_to_resp() {
+        assert_eq!(to_resp(&42i64).unwrap(), RespFrame::Integer(42));
+        assert_eq!(to_resp(&true).unwrap(), RespFrame::Boolean(true));
+        assert_eq!(
+            to_resp(&"hi").unwrap(),
+            RespFrame::BulkString(BulkString(b"hi".to_vec()))
+        );
+        assert_eq!(to_resp(&Option::<i64>::None).unwrap(), RespFrame::Null(RespNull));
+    }
+
+    #[test]
+    fn test_seq_to_resp() {
+        let frame = to_resp(&vec![1i64, 2, 3]).unwrap();
+        assert_eq!(
+            frame,
+            RespFrame::Array(Array(vec![
+                RespFrame::Integer(1),
+                RespFrame::Integer(2),
+                RespFrame::Integer(3),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_struct_roundtrip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Point {
+            x: i64,
+            y: i64,
+            label: String,
+        }
+
+        let point = Point {
+            x: 1,
+            y: -2,
+            label: "origin".to_string(),
+        };
+        let frame = to_resp(&point).unwrap();
+        let back: Point = from_resp(frame).unwrap();
+        assert_eq!(point, back);
+    }
+
+    #[test]
+    fn test_primitive_roundtrip() {
+        let frame = to_resp(&123i64).unwrap();
+        let back: i64 = from_resp(frame).unwrap();
+        assert_eq!(back, 123);
+    }
+}